@@ -7,31 +7,585 @@ mod vfs {
 
     use std::path::PathBuf;
     use std::pin::Pin;
+    use std::time::SystemTime;
 
     use tokio_stream::Stream;
 
     /// A generic stream of PathBuf's.
     pub type PathStream = Pin<Box<dyn Stream<Item = anyhow::Result<PathBuf>> + Send>>;
 
+    /// A generic stream of [`VfsEntry`]'s.
+    pub type EntryStream = Pin<Box<dyn Stream<Item = anyhow::Result<VfsEntry>> + Send>>;
+
+    /// A backend-agnostic entry classification.
+    ///
+    /// A portable stand-in for [`std::fs::FileType`] (which has no public
+    /// constructor) so object-store backends can label entries — and delimiter
+    /// listings can emit pseudo-directory markers — without a local `stat`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EntryType {
+        /// A regular file / object leaf.
+        File,
+        /// A directory or common-prefix marker.
+        Dir,
+        /// A symbolic link (local backends only).
+        Symlink,
+        /// Anything else the backend does not distinguish.
+        Other,
+    }
+
+    impl EntryType {
+        /// Whether this entry is a directory (or directory-like prefix marker).
+        pub fn is_dir(self) -> bool {
+            matches!(self, EntryType::Dir)
+        }
+
+        /// Whether this entry is a regular file.
+        pub fn is_file(self) -> bool {
+            matches!(self, EntryType::File)
+        }
+    }
+
+    impl From<std::fs::FileType> for EntryType {
+        fn from(ft: std::fs::FileType) -> Self {
+            if ft.is_dir() {
+                EntryType::Dir
+            } else if ft.is_file() {
+                EntryType::File
+            } else if ft.is_symlink() {
+                EntryType::Symlink
+            } else {
+                EntryType::Other
+            }
+        }
+    }
+
+    /// A directory entry carrying the metadata `readdir` can surface cheaply.
+    ///
+    /// `file_type` is always populated; `len` and `modified` are filled in only
+    /// when the backend already has them at no extra cost (an object store's
+    /// stored length, or a `stat` it made anyway), so callers that just need
+    /// names and types never pay for an extra syscall.
+    #[derive(Debug, Clone)]
+    pub struct VfsEntry {
+        /// Full path to the entry.
+        pub path: PathBuf,
+        /// The entry's type, from `readdir` where possible.
+        pub file_type: EntryType,
+        /// File length in bytes, if known.
+        pub len: Option<u64>,
+        /// Last modification time, if known.
+        pub modified: Option<SystemTime>,
+    }
+
     /// A generic VFS specification.
     #[async_trait::async_trait]
     pub trait Vfs: Send + Sync + 'static {
         /// List all files in a directory.
-        async fn list_files(&self, path: &std::path::Path) -> PathStream;
+        ///
+        /// Opening the directory is fallible — a missing or unreadable path
+        /// surfaces as an `Err` rather than aborting the process — while errors
+        /// reading individual entries are reported per item on the stream.
+        async fn list_files(&self, path: &std::path::Path) -> anyhow::Result<PathStream>;
+
+        /// List a directory as rich [`VfsEntry`]'s with cached metadata.
+        ///
+        /// Opening the directory is fallible for the same reason as
+        /// [`list_files`](Vfs::list_files): a missing or unreadable path surfaces
+        /// as an `Err` rather than aborting the process.
+        async fn list_entries(
+            &self,
+            path: &std::path::Path,
+        ) -> anyhow::Result<EntryStream>;
+
+        /// List objects under a key `prefix`, object-store style.
+        ///
+        /// With no `delimiter` every key under `prefix` is streamed as a leaf.
+        /// With a delimiter (usually `"/"`), keys that share the next path
+        /// segment after `prefix` are collapsed into a single common-prefix
+        /// directory marker ([`EntryType::Dir`]) rather than listing every leaf
+        /// beneath them — the flat-namespace emulation of directories.
+        async fn list_with_prefix(
+            &self,
+            prefix: &str,
+            delimiter: Option<&str>,
+        ) -> anyhow::Result<EntryStream>;
+
+        /// Recursively stream every file in the subtree rooted at `path`.
+        ///
+        /// When `follow_symlinks` is set, directory symlinks are descended into
+        /// and a visited set guards against cycles; otherwise symlinks are
+        /// classified by [`symlink_metadata`](tokio::fs::symlink_metadata) and
+        /// never traversed.
+        async fn walk(&self, path: &std::path::Path, follow_symlinks: bool) -> PathStream;
+
+        /// Fill in an entry's `len`/`modified` fields, if the backend can.
+        ///
+        /// The default is a no-op so flat stores keep whatever they already
+        /// know; local backends override it with a `stat`. [`list_filtered`]
+        /// calls it only when a size/mtime sort or `min_modified` filter needs
+        /// the data, so name-only listings still pay for no extra syscall.
+        ///
+        /// [`list_filtered`]: Vfs::list_filtered
+        async fn fill_metadata(&self, entry: VfsEntry) -> anyhow::Result<VfsEntry> {
+            Ok(entry)
+        }
+
+        /// List a directory applying [`ListOptions`] (glob, type, mtime, order).
+        ///
+        /// Unordered ([`Ordering::AsReturned`]) listings that need no metadata
+        /// stream lazily through a filter stage; any sorted order, or a
+        /// `min_modified` filter, buffers into a `Vec`, sorts by the chosen key
+        /// and re-emits. Entries are enriched via [`fill_metadata`] only for a
+        /// size/mtime sort or a `min_modified` filter. Glob
+        /// matching is against each entry's final path component, so it composes
+        /// with [`walk`](Vfs::walk) for whole-tree filtered search.
+        ///
+        /// [`fill_metadata`]: Vfs::fill_metadata
+        async fn list_filtered(
+            &self,
+            path: &std::path::Path,
+            opts: ListOptions,
+        ) -> anyhow::Result<EntryStream> {
+            use tokio_stream::StreamExt;
+
+            let needs_meta = opts.min_modified.is_some()
+                || matches!(opts.order, Ordering::BySize | Ordering::ByModified);
+
+            let mut entries = self.list_entries(path).await?;
+
+            // Fast path: lazy filter, no buffering, no extra `stat`.
+            if !needs_meta && matches!(opts.order, Ordering::AsReturned) {
+                return Ok(Box::pin(entries.filter(move |res| match res {
+                    Ok(entry) => opts.matches(entry),
+                    Err(_) => true,
+                })));
+            }
+
+            // Buffered path: enrich, filter, then sort by the chosen key.
+            let mut errors: Vec<anyhow::Result<VfsEntry>> = Vec::new();
+            let mut kept: Vec<VfsEntry> = Vec::new();
+            while let Some(res) = entries.next().await {
+                match res {
+                    Ok(entry) => {
+                        let entry = if needs_meta {
+                            match self.fill_metadata(entry).await {
+                                Ok(entry) => entry,
+                                Err(e) => {
+                                    errors.push(Err(e));
+                                    continue;
+                                }
+                            }
+                        } else {
+                            entry
+                        };
+                        if opts.matches(&entry) {
+                            kept.push(entry);
+                        }
+                    }
+                    Err(e) => errors.push(Err(e)),
+                }
+            }
+
+            match opts.order {
+                Ordering::AsReturned => {}
+                Ordering::ByName => kept.sort_by(|a, b| a.path.cmp(&b.path)),
+                Ordering::BySize => kept.sort_by_key(|e| e.len.unwrap_or(0)),
+                Ordering::ByModified => {
+                    kept.sort_by_key(|e| e.modified.unwrap_or(std::time::UNIX_EPOCH))
+                }
+            }
+
+            // Surface per-entry read/metadata errors first, then the ordered
+            // entries. (An open error was already returned above.)
+            let out: Vec<_> = errors.into_iter().chain(kept.into_iter().map(Ok)).collect();
+            Ok(Box::pin(tokio_stream::iter(out)))
+        }
+    }
+
+    /// Sort key applied by [`Vfs::list_filtered`].
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum Ordering {
+        /// Backend order — the lazy, unbuffered default.
+        #[default]
+        AsReturned,
+        /// Sort by full path.
+        ByName,
+        /// Sort ascending by `len` (unknown sizes sort first).
+        BySize,
+        /// Sort ascending by `modified` (unknown mtimes sort first).
+        ByModified,
+    }
+
+    /// Declarative options for [`Vfs::list_filtered`].
+    #[derive(Debug, Clone, Default)]
+    pub struct ListOptions {
+        /// Glob matched against each entry's final path component.
+        pub glob: Option<glob::Pattern>,
+        /// If set, keep only these entry types.
+        pub include_types: Option<Vec<EntryType>>,
+        /// Drop these entry types.
+        pub exclude_types: Vec<EntryType>,
+        /// Keep only entries modified at or after this instant.
+        pub min_modified: Option<SystemTime>,
+        /// How to order the results.
+        pub order: Ordering,
+    }
+
+    impl ListOptions {
+        /// Whether `entry` passes the glob, type and mtime filters.
+        pub fn matches(&self, entry: &VfsEntry) -> bool {
+            if let Some(pattern) = &self.glob {
+                match entry.path.file_name().map(|n| n.to_string_lossy()) {
+                    Some(name) if pattern.matches(&name) => {}
+                    _ => return false,
+                }
+            }
+            if let Some(include) = &self.include_types {
+                if !include.contains(&entry.file_type) {
+                    return false;
+                }
+            }
+            if self.exclude_types.contains(&entry.file_type) {
+                return false;
+            }
+            if let Some(min) = self.min_modified {
+                match entry.modified {
+                    Some(m) if m >= min => {}
+                    _ => return false,
+                }
+            }
+            true
+        }
     }
 }
 
+#[derive(Clone)]
 struct TokioVfs;
 
 #[async_trait::async_trait]
 impl vfs::Vfs for TokioVfs {
-    async fn list_files(&self, path: &std::path::Path) -> vfs::PathStream {
+    async fn list_files(&self, path: &std::path::Path) -> anyhow::Result<vfs::PathStream> {
+        /// Entries pulled per blocking round-trip, to amortize the thread-pool
+        /// handoff across many `readdir` results instead of paying it per entry.
+        const CHUNK: usize = 32;
+
+        let path = path.to_path_buf();
+        // Propagate the open error instead of `unwrap`-ing it.
+        let mut reader = tokio::task::spawn_blocking({
+            let path = path.clone();
+            move || std::fs::read_dir(&path)
+        })
+        .await
+        .context("join read_dir open")?
+        .with_context(|| format!("read_dir {}", path.display()))?;
+
+        let stream = async_stream::stream! {
+            loop {
+                // Drain a fixed-size chunk in one blocking hop, handing the
+                // iterator back so the next round can resume from it.
+                let joined = tokio::task::spawn_blocking(move || {
+                    let mut batch = Vec::with_capacity(CHUNK);
+                    for _ in 0..CHUNK {
+                        match reader.next() {
+                            Some(res) => batch.push(res),
+                            None => return (reader, batch, true),
+                        }
+                    }
+                    (reader, batch, false)
+                })
+                .await;
+
+                let (back, batch, done) = match joined {
+                    Ok(t) => t,
+                    Err(e) => {
+                        yield Err(anyhow::Error::new(e).context("join read_dir chunk"));
+                        break;
+                    }
+                };
+                reader = back;
+
+                for res in batch {
+                    yield res.context("read_dir entry").map(|e| e.path());
+                }
+                if done {
+                    break;
+                }
+            }
+        };
+        Ok(Box::pin(stream))
+    }
+
+    async fn list_entries(
+        &self,
+        path: &std::path::Path,
+    ) -> anyhow::Result<vfs::EntryStream> {
         use tokio_stream::StreamExt;
 
-        let read_dir = tokio::fs::read_dir(path).await.unwrap();
+        // Propagate the open error instead of `unwrap`-ing it.
+        let read_dir = tokio::fs::read_dir(path)
+            .await
+            .with_context(|| format!("read_dir {}", path.display()))?;
         let stream = tokio_stream::wrappers::ReadDirStream::new(read_dir);
-        let stream = stream.map(|entry| entry.context("context").map(|x| x.path()));
-        Box::pin(stream)
+        // `file_type()` is free on platforms that return the type from
+        // `readdir`; `len`/`modified` stay `None` until a caller actually needs
+        // them so the common name-only listing pays for no extra `stat`.
+        let stream = stream.then(|entry| async move {
+            let entry = entry.context("read_dir entry")?;
+            let file_type = entry
+                .file_type()
+                .await
+                .with_context(|| format!("file_type {}", entry.path().display()))?;
+            Ok(vfs::VfsEntry {
+                path: entry.path(),
+                file_type: file_type.into(),
+                len: None,
+                modified: None,
+            })
+        });
+        Ok(Box::pin(stream))
+    }
+
+    async fn list_with_prefix(
+        &self,
+        prefix: &str,
+        delimiter: Option<&str>,
+    ) -> anyhow::Result<vfs::EntryStream> {
+        use tokio_stream::StreamExt;
+
+        let prefix = std::path::Path::new(prefix);
+        match delimiter {
+            // No delimiter: every key under the prefix is a leaf, so descend the
+            // whole subtree. Both backends normalize the prefix to a segment
+            // boundary first (`TokioVfs` via `walk`, `InMemoryVfs` explicitly),
+            // so `(prefix, None)` means the same thing on either. `walk` only
+            // yields files, so each path is labelled a file.
+            None => {
+                let stream = self.walk(prefix, false).await.map(|res| {
+                    res.map(|path| vfs::VfsEntry {
+                        path,
+                        file_type: vfs::EntryType::File,
+                        len: None,
+                        modified: None,
+                    })
+                });
+                Ok(Box::pin(stream))
+            }
+            // With a delimiter the listing collapses to one level: the filesystem
+            // hierarchy already provides the common-prefix markers as real
+            // directories, which `list_entries` labels for us.
+            Some(_) => self.list_entries(prefix).await,
+        }
+    }
+
+    async fn fill_metadata(&self, mut entry: vfs::VfsEntry) -> anyhow::Result<vfs::VfsEntry> {
+        // Only touch the disk for fields we do not already have.
+        if entry.len.is_none() || entry.modified.is_none() {
+            let meta = tokio::fs::metadata(&entry.path)
+                .await
+                .with_context(|| format!("metadata {}", entry.path.display()))?;
+            if entry.len.is_none() {
+                entry.len = Some(meta.len());
+            }
+            if entry.modified.is_none() {
+                entry.modified = meta.modified().ok();
+            }
+        }
+        Ok(entry)
+    }
+
+    async fn walk(&self, path: &std::path::Path, follow_symlinks: bool) -> vfs::PathStream {
+        use std::collections::{HashSet, VecDeque};
+        use std::path::PathBuf;
+
+        use tokio_stream::StreamExt;
+
+        // Recursing with an `async fn` returning `impl Stream` does not compile
+        // (each level has a distinct return type), so we keep one concrete
+        // `PathStream` and drive the descent from an explicit worklist. An owned
+        // clone of the backend is moved into the stream so it can re-enter its
+        // own `list_files` without borrowing `self`.
+        let backend = self.clone();
+        let mut queue: VecDeque<PathBuf> = VecDeque::new();
+        queue.push_back(path.to_path_buf());
+
+        Box::pin(async_stream::stream! {
+            let mut visited: HashSet<PathBuf> = HashSet::new();
+
+            while let Some(dir) = queue.pop_front() {
+                // Skip directories already descended into so a symlink cycle
+                // cannot spin forever.
+                match tokio::fs::canonicalize(&dir).await {
+                    Ok(real) => {
+                        if !visited.insert(real) {
+                            continue;
+                        }
+                    }
+                    Err(e) => {
+                        yield Err(anyhow::Error::new(e)
+                            .context(format!("canonicalize {}", dir.display())));
+                        continue;
+                    }
+                }
+
+                let mut entries = match backend.list_files(&dir).await {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        yield Err(e);
+                        continue;
+                    }
+                };
+                while let Some(entry) = entries.next().await {
+                    let child = match entry {
+                        Ok(child) => child,
+                        Err(e) => {
+                            yield Err(e);
+                            continue;
+                        }
+                    };
+
+                    let meta = if follow_symlinks {
+                        tokio::fs::metadata(&child).await
+                    } else {
+                        tokio::fs::symlink_metadata(&child).await
+                    };
+                    match meta {
+                        Ok(meta) if meta.is_dir() => queue.push_back(child),
+                        Ok(_) => yield Ok(child),
+                        Err(e) => yield Err(anyhow::Error::new(e)
+                            .context(format!("stat {}", child.display()))),
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// An in-memory object store over a sorted key/value map.
+///
+/// Keys are flat object paths (e.g. `"a/b/c.txt"`); their sorted order makes a
+/// prefix scan a `BTreeMap` range query and delimiter grouping a single forward
+/// pass. Directories are purely synthetic — they exist only as common prefixes
+/// of the keys.
+#[derive(Clone, Default)]
+struct InMemoryVfs {
+    objects: std::sync::Arc<std::collections::BTreeMap<String, bytes::Bytes>>,
+}
+
+impl InMemoryVfs {
+    /// Build a store from an iterator of key/value pairs.
+    fn new(objects: impl IntoIterator<Item = (String, bytes::Bytes)>) -> Self {
+        Self {
+            objects: std::sync::Arc::new(objects.into_iter().collect()),
+        }
+    }
+
+    /// Collect the entries under `prefix`, optionally collapsing on `delimiter`.
+    fn scan(&self, prefix: &str, delimiter: Option<&str>) -> Vec<vfs::VfsEntry> {
+        use std::collections::BTreeSet;
+
+        let mut out = Vec::new();
+        let mut seen_prefixes: BTreeSet<String> = BTreeSet::new();
+
+        for (key, value) in self.objects.range(prefix.to_string()..) {
+            if !key.starts_with(prefix) {
+                break; // sorted keys: past the prefix range.
+            }
+            let rest = &key[prefix.len()..];
+
+            match delimiter.and_then(|d| rest.find(d).map(|i| i + d.len())) {
+                // A delimiter splits off a common prefix: emit it once as a dir.
+                Some(cut) => {
+                    let common = &key[..prefix.len() + cut];
+                    if seen_prefixes.insert(common.to_string()) {
+                        out.push(vfs::VfsEntry {
+                            path: std::path::PathBuf::from(common),
+                            file_type: vfs::EntryType::Dir,
+                            len: None,
+                            modified: None,
+                        });
+                    }
+                }
+                // A plain leaf object; its length is free from the map.
+                None => out.push(vfs::VfsEntry {
+                    path: std::path::PathBuf::from(key),
+                    file_type: vfs::EntryType::File,
+                    len: Some(value.len() as u64),
+                    modified: None,
+                }),
+            }
+        }
+
+        out
+    }
+}
+
+#[async_trait::async_trait]
+impl vfs::Vfs for InMemoryVfs {
+    async fn list_files(&self, path: &std::path::Path) -> anyhow::Result<vfs::PathStream> {
+        // One directory level: treat the path as a key prefix terminated by a
+        // delimiter and drop the synthetic directory markers. An in-memory scan
+        // cannot fail to open, so the fallible signature is satisfied trivially.
+        let mut prefix = path.to_string_lossy().into_owned();
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        let paths: Vec<_> = self
+            .scan(&prefix, Some("/"))
+            .into_iter()
+            .filter(|e| e.file_type.is_file())
+            .map(|e| Ok(e.path))
+            .collect();
+        Ok(Box::pin(tokio_stream::iter(paths)))
+    }
+
+    async fn list_entries(
+        &self,
+        path: &std::path::Path,
+    ) -> anyhow::Result<vfs::EntryStream> {
+        let mut prefix = path.to_string_lossy().into_owned();
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        let entries: Vec<_> = self.scan(&prefix, Some("/")).into_iter().map(Ok).collect();
+        // An in-memory scan cannot fail to open, so the fallible signature is
+        // satisfied trivially.
+        Ok(Box::pin(tokio_stream::iter(entries)))
+    }
+
+    async fn list_with_prefix(
+        &self,
+        prefix: &str,
+        delimiter: Option<&str>,
+    ) -> anyhow::Result<vfs::EntryStream> {
+        // Normalize to a segment boundary (as `list_files`/`list_entries`/`walk`
+        // do) so a non-slash prefix like `"logs/2024"` means the same `logs/2024/`
+        // subtree here as it does through `TokioVfs`'s filesystem-path translation,
+        // rather than also matching a sibling `logs/2024-backup/...` key.
+        let mut prefix = prefix.to_string();
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        let entries: Vec<_> = self.scan(&prefix, delimiter).into_iter().map(Ok).collect();
+        Ok(Box::pin(tokio_stream::iter(entries)))
+    }
+
+    async fn walk(&self, path: &std::path::Path, _follow_symlinks: bool) -> vfs::PathStream {
+        // A flat key store has no cycles and no symlinks: an un-delimited prefix
+        // scan already yields every leaf in the subtree. Terminate the prefix on
+        // a segment boundary (as `list_files`/`list_entries` do) so a raw string
+        // prefix cannot match a sibling key across a segment boundary — e.g.
+        // `walk("a")` must not pull in `abc/d`.
+        let mut prefix = path.to_string_lossy().into_owned();
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        let paths: Vec<_> = self
+            .scan(&prefix, None)
+            .into_iter()
+            .map(|e| Ok(e.path))
+            .collect();
+        Box::pin(tokio_stream::iter(paths))
     }
 }
 
@@ -45,11 +599,225 @@ async fn main() {
 
     let root = std::env::current_dir().unwrap();
     let vfs = TokioVfs;
-    let mut stream = vfs.list_files(&root).await;
+    let mut stream = match vfs.list_files(&root).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            tracing::error!(error = ?e, "failed to list directory");
+            return;
+        }
+    };
 
     while let Some(path) = stream.next().await {
         tracing::info!(?path);
     }
 
+    // Demonstrate the richer listing API over an in-memory object store so the
+    // entry/prefix/filter/walk paths are exercised without depending on the
+    // local tree's layout.
+    let store = InMemoryVfs::new(
+        [
+            ("logs/2024/jan.log", "hello"),
+            ("logs/2024/feb.log", "worldwide"),
+            ("logs/2024-backup/jan.log", "x"),
+            ("logs/2025/jan.log", "hi"),
+            ("readme.md", "docs"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), bytes::Bytes::from_static(v.as_bytes()))),
+    );
+
+    // Rich entries for one level, classified by `EntryType`.
+    if let Ok(mut entries) = store.list_entries(std::path::Path::new("logs")).await {
+        while let Some(Ok(entry)) = entries.next().await {
+            let kind = if entry.file_type.is_dir() { "dir" } else { "file" };
+            tracing::info!(path = %entry.path.display(), kind, "entry");
+        }
+    }
+
+    // Delimiter listing collapses common prefixes; `"logs/2024"` normalizes to
+    // the `logs/2024/` segment, not the sibling `logs/2024-backup/`.
+    if let Ok(mut grouped) = store.list_with_prefix("logs/2024", Some("/")).await {
+        while let Some(Ok(entry)) = grouped.next().await {
+            tracing::info!(prefix = %entry.path.display(), "grouped");
+        }
+    }
+
+    // Recursive walk streams every leaf in the subtree.
+    let mut walked = store.walk(std::path::Path::new("logs"), false).await;
+    while let Some(Ok(path)) = walked.next().await {
+        tracing::info!(path = %path.display(), "walked");
+    }
+
+    // Filtered + ordered listing: `*.log` smallest first.
+    let opts = vfs::ListOptions {
+        glob: Some(glob::Pattern::new("*.log").unwrap()),
+        order: vfs::Ordering::BySize,
+        ..Default::default()
+    };
+    if let Ok(mut filtered) = store.list_filtered(std::path::Path::new("logs/2024"), opts).await {
+        while let Some(Ok(entry)) = filtered.next().await {
+            tracing::info!(path = %entry.path.display(), len = ?entry.len, "filtered");
+        }
+    }
+
     tracing::info!("done");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_stream::StreamExt;
+    use vfs::Vfs;
+
+    /// Build an in-memory store from `(key, contents)` pairs.
+    fn store(pairs: &[(&str, &str)]) -> InMemoryVfs {
+        InMemoryVfs::new(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), bytes::Bytes::copy_from_slice(v.as_bytes()))),
+        )
+    }
+
+    async fn paths(stream: vfs::PathStream) -> Vec<String> {
+        let mut stream = stream;
+        let mut out = Vec::new();
+        while let Some(res) = stream.next().await {
+            out.push(res.unwrap().to_string_lossy().into_owned());
+        }
+        out
+    }
+
+    async fn entries(stream: vfs::EntryStream) -> Vec<vfs::VfsEntry> {
+        let mut stream = stream;
+        let mut out = Vec::new();
+        while let Some(res) = stream.next().await {
+            out.push(res.unwrap());
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn list_with_prefix_delimiter_groups_common_prefixes() {
+        let vfs = store(&[
+            ("a/b", "xx"),
+            ("a/c", "z"),
+            ("a/sub/e", "q"),
+            ("a/sub/f", "r"),
+            ("abc/d", "w"),
+        ]);
+        let got = entries(vfs.list_with_prefix("a/", Some("/")).await.unwrap()).await;
+        let named: Vec<_> = got
+            .iter()
+            .map(|e| (e.path.to_string_lossy().into_owned(), e.file_type))
+            .collect();
+        assert_eq!(
+            named,
+            vec![
+                ("a/b".to_string(), vfs::EntryType::File),
+                ("a/c".to_string(), vfs::EntryType::File),
+                // The two keys under `a/sub/` collapse into one dir marker.
+                ("a/sub/".to_string(), vfs::EntryType::Dir),
+            ],
+        );
+        // The sibling `abc/d` shares the raw `a` prefix but not the `a/` segment.
+        assert!(named.iter().all(|(p, _)| !p.starts_with("abc")));
+    }
+
+    #[tokio::test]
+    async fn list_with_prefix_no_delimiter_streams_all_leaves() {
+        let vfs = store(&[("a/b", "x"), ("a/sub/e", "y"), ("abc/d", "z")]);
+        let got = entries(vfs.list_with_prefix("a/", None).await.unwrap()).await;
+        let names: Vec<_> = got.iter().map(|e| e.path.to_string_lossy().into_owned()).collect();
+        assert_eq!(names, vec!["a/b", "a/sub/e"]);
+    }
+
+    #[tokio::test]
+    async fn walk_yields_subtree_and_respects_segment_boundary() {
+        let vfs = store(&[("a/b", "x"), ("a/c", "y"), ("a/sub/e", "z"), ("abc/d", "w")]);
+        // `walk("a")` must descend `a/`'s subtree without leaking the sibling
+        // key `abc/d` that shares the raw `a` prefix.
+        let got = paths(vfs.walk(std::path::Path::new("a"), false).await).await;
+        assert_eq!(got, vec!["a/b", "a/c", "a/sub/e"]);
+    }
+
+    #[tokio::test]
+    async fn list_filtered_glob_matches_final_component() {
+        let vfs = store(&[("g/one.rs", "x"), ("g/two.txt", "y"), ("g/three.rs", "z")]);
+        let opts = vfs::ListOptions {
+            glob: Some(glob::Pattern::new("*.rs").unwrap()),
+            ..Default::default()
+        };
+        let got = entries(vfs.list_filtered(std::path::Path::new("g"), opts).await.unwrap()).await;
+        let mut names: Vec<_> = got.iter().map(|e| e.path.to_string_lossy().into_owned()).collect();
+        names.sort();
+        assert_eq!(names, vec!["g/one.rs", "g/three.rs"]);
+    }
+
+    #[tokio::test]
+    async fn list_filtered_orderings() {
+        let vfs = store(&[("d/bbb", "x"), ("d/aa", "yyy"), ("d/c", "zz")]);
+        let files_only = || vfs::ListOptions {
+            include_types: Some(vec![vfs::EntryType::File]),
+            ..Default::default()
+        };
+
+        let as_returned = entries(
+            vfs.list_filtered(std::path::Path::new("d"), files_only())
+                .await
+                .unwrap(),
+        )
+        .await;
+        let names: Vec<_> = as_returned
+            .iter()
+            .map(|e| e.path.to_string_lossy().into_owned())
+            .collect();
+        // `AsReturned` follows the store's sorted key order.
+        assert_eq!(names, vec!["d/aa", "d/bbb", "d/c"]);
+
+        let by_name = entries(
+            vfs.list_filtered(
+                std::path::Path::new("d"),
+                vfs::ListOptions {
+                    order: vfs::Ordering::ByName,
+                    ..files_only()
+                },
+            )
+            .await
+            .unwrap(),
+        )
+        .await;
+        let names: Vec<_> = by_name.iter().map(|e| e.path.to_string_lossy().into_owned()).collect();
+        assert_eq!(names, vec!["d/aa", "d/bbb", "d/c"]);
+
+        let by_size = entries(
+            vfs.list_filtered(
+                std::path::Path::new("d"),
+                vfs::ListOptions {
+                    order: vfs::Ordering::BySize,
+                    ..files_only()
+                },
+            )
+            .await
+            .unwrap(),
+        )
+        .await;
+        let sizes: Vec<_> = by_size.iter().map(|e| e.len.unwrap()).collect();
+        // Sorted ascending by the map-provided object length.
+        assert_eq!(sizes, vec![1, 2, 3]);
+
+        let by_modified = entries(
+            vfs.list_filtered(
+                std::path::Path::new("d"),
+                vfs::ListOptions {
+                    order: vfs::Ordering::ByModified,
+                    ..files_only()
+                },
+            )
+            .await
+            .unwrap(),
+        )
+        .await;
+        // The flat store has no mtimes; ordering is total and keeps every entry.
+        assert_eq!(by_modified.len(), 3);
+    }
+}